@@ -25,7 +25,12 @@ impl Debug for Record {
 
 impl Display for Record {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{} == {}", self.n, self.e)
+        if self.n.is_integer() {
+            write!(f, "{} == {}", self.n.to_integer(), self.e)
+        }
+        else {
+            write!(f, "{} == {}", self.n, self.e)
+        }
     }
 }
 
@@ -39,54 +44,65 @@ impl Debug for Expression {
             Self::Minus { lhs, rhs } => f.debug_struct("Minus").field("lhs", lhs).field("rhs", rhs).finish(),
             Self::Times { lhs, rhs } => f.debug_struct("Times").field("lhs", lhs).field("rhs", rhs).finish(),
             Self::Divide { lhs, rhs } => f.debug_struct("Divide").field("lhs", lhs).field("rhs", rhs).finish(),
+            Self::Modulo { lhs, rhs } => f.debug_struct("Modulo").field("lhs", lhs).field("rhs", rhs).finish(),
+            Self::Power { lhs, rhs } => f.debug_struct("Power").field("lhs", lhs).field("rhs", rhs).finish(),
+            Self::Abs { base } => f.debug_struct("Abs").field("base", base).finish(),
         }
     }
 }
 
 impl Display for Expression {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let p = self.precedence();
         match self {
             Self::Atomic { number } => Display::fmt(number, f)?,
-            Self::Negative { base: lhs } => {
-                if lhs.lower_than_mul() {
-                    write!(f, "-({lhs})")?
-                }
-                else {
-                    write!(f, "-{lhs}")?
-                }
+            Self::Negative { base } => {
+                f.write_char('-')?;
+                base.fmt_child(f, Some(p))?;
+            }
+            Self::Concat { lhs, rhs } => {
+                lhs.fmt_child(f, Some(p))?;
+                rhs.fmt_child(f, Some(p))?;
+            }
+            Self::Plus { lhs, rhs } => {
+                lhs.fmt_child(f, Some(p))?;
+                f.write_char('+')?;
+                rhs.fmt_child(f, Some(p))?;
             }
-            Self::Concat { lhs, rhs } => write!(f, "{lhs}{rhs}")?,
-            Self::Plus { lhs, rhs } => write!(f, "{lhs}+{rhs}")?,
             Self::Minus { lhs, rhs } => {
-                if rhs.lower_than_mul() {
-                    write!(f, "{lhs}-({rhs})")?
-                }
-                else {
-                    write!(f, "{lhs}-{rhs}")?
-                }
+                lhs.fmt_child(f, Some(p))?;
+                f.write_char('-')?;
+                // left-associative: `a-(b-c)` must stay parenthesized, so the rhs sees a stricter threshold.
+                rhs.fmt_child(f, Some(p - 1))?;
             }
             Self::Times { lhs, rhs } => {
-                if lhs.lower_than_mul() {
-                    write!(f, "({lhs})")?
-                }
-                else {
-                    write!(f, "{lhs}")?
-                }
+                lhs.fmt_child(f, Some(p))?;
                 f.write_char('×')?;
-                if rhs.lower_than_mul() { write!(f, "({rhs})")? } else { write!(f, "{rhs}")? }
+                // `a×(b%c)` must stay parenthesized, so the rhs sees a stricter threshold.
+                rhs.fmt_child(f, Some(p - 1))?;
             }
             Self::Divide { lhs, rhs } => {
-                if lhs.lower_than_mul() {
-                    write!(f, "({lhs})")?
-                }
-                else {
-                    write!(f, "{lhs}")?
-                }
+                lhs.fmt_child(f, Some(p))?;
                 f.write_char('÷')?;
-                match &**rhs {
-                    Self::Atomic { .. } => write!(f, "{rhs}")?,
-                    _ => write!(f, "({rhs})")?,
-                }
+                // left-associative: `a÷(b÷c)` must stay parenthesized, so the rhs sees a stricter threshold.
+                rhs.fmt_child(f, Some(p - 1))?;
+            }
+            Self::Modulo { lhs, rhs } => {
+                lhs.fmt_child(f, Some(p))?;
+                f.write_char('%')?;
+                // left-associative: `a%(b%c)` must stay parenthesized, so the rhs sees a stricter threshold.
+                rhs.fmt_child(f, Some(p - 1))?;
+            }
+            Self::Power { lhs, rhs } => {
+                // right-associative: `(a^b)^c` must stay parenthesized, so the lhs sees a stricter threshold.
+                lhs.fmt_child(f, Some(p - 1))?;
+                f.write_char('^')?;
+                rhs.fmt_child(f, Some(p))?;
+            }
+            Self::Abs { base } => {
+                f.write_char('|')?;
+                base.fmt_child(f, None)?;
+                f.write_char('|')?;
             }
         }
         Ok(())
@@ -94,19 +110,24 @@ impl Display for Expression {
 }
 
 impl Expression {
-    fn lower_than_atom(&self) -> bool {
+    /// Binding strength of this node's root operator: lower binds tighter. Used to decide whether
+    /// a child needs parenthesizing when nested inside a parent operator of a given precedence.
+    fn precedence(&self) -> u8 {
         match self {
-            Self::Atomic { .. } => true,
-            _ => false,
+            Self::Atomic { .. } | Self::Concat { .. } | Self::Abs { .. } => 0,
+            Self::Negative { .. } | Self::Power { .. } => 1,
+            Self::Times { .. } | Self::Divide { .. } | Self::Modulo { .. } => 2,
+            Self::Plus { .. } | Self::Minus { .. } => 3,
         }
     }
 
-    fn lower_than_mul(&self) -> bool {
-        match self {
-            Self::Plus { .. } => true,
-            Self::Minus { .. } => true,
-            Self::Divide { .. } => true,
-            _ => false,
+    /// Format this node as a child of a parent with the given precedence (`None` at the root means
+    /// never parenthesize), wrapping in parentheses exactly when this node binds looser than the
+    /// parent requires.
+    fn fmt_child(&self, f: &mut Formatter<'_>, parent_precedence: Option<u8>) -> std::fmt::Result {
+        match parent_precedence {
+            Some(threshold) if self.precedence() > threshold => write!(f, "({self})"),
+            _ => Display::fmt(self, f),
         }
     }
 }
@@ -0,0 +1,78 @@
+use super::*;
+use num_rational::Ratio;
+use num_traits::{Signed, Zero};
+
+/// Errors that can occur while evaluating an [`Expression`] exactly.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EvalError {
+    /// The right-hand side of a `÷` evaluated to zero, or zero was raised to a negative power.
+    DivideByZero,
+    /// The right-hand side of a `%` evaluated to zero.
+    ModuloByZero,
+    /// `Concat` or `%` was applied to an operand that is not a whole number.
+    NonIntegerOperand,
+    /// `Power` was applied to an exponent that is not a whole number.
+    NonIntegerExponent,
+}
+
+impl Display for EvalError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DivideByZero => write!(f, "division by zero"),
+            Self::ModuloByZero => write!(f, "modulo by zero"),
+            Self::NonIntegerOperand => write!(f, "expected a whole-number operand"),
+            Self::NonIntegerExponent => write!(f, "expected a whole-number exponent"),
+        }
+    }
+}
+
+impl std::error::Error for EvalError {}
+
+pub(super) fn require_integer(value: Ratio<i64>) -> Result<i64, EvalError> {
+    if value.is_integer() { Ok(value.to_integer()) } else { Err(EvalError::NonIntegerOperand) }
+}
+
+pub(super) fn digit_count(n: i64) -> u32 {
+    if n == 0 { 1 } else { n.unsigned_abs().ilog10() + 1 }
+}
+
+impl Expression {
+    /// Evaluate this expression exactly, never losing precision to truncating division.
+    pub fn evaluate(&self) -> Result<Ratio<i64>, EvalError> {
+        match self {
+            Self::Atomic { number } => Ok(Ratio::from_integer(*number)),
+            Self::Negative { base } => Ok(-base.evaluate()?),
+            Self::Concat { lhs, rhs } => {
+                let lhs = require_integer(lhs.evaluate()?)?;
+                let rhs = require_integer(rhs.evaluate()?)?;
+                Ok(Ratio::from_integer(lhs * 10i64.pow(digit_count(rhs)) + rhs))
+            }
+            Self::Plus { lhs, rhs } => Ok(lhs.evaluate()? + rhs.evaluate()?),
+            Self::Minus { lhs, rhs } => Ok(lhs.evaluate()? - rhs.evaluate()?),
+            Self::Times { lhs, rhs } => Ok(lhs.evaluate()? * rhs.evaluate()?),
+            Self::Divide { lhs, rhs } => {
+                let rhs = rhs.evaluate()?;
+                if rhs.is_zero() { Err(EvalError::DivideByZero) } else { Ok(lhs.evaluate()? / rhs) }
+            }
+            Self::Modulo { lhs, rhs } => {
+                let lhs = require_integer(lhs.evaluate()?)?;
+                let rhs = require_integer(rhs.evaluate()?)?;
+                if rhs == 0 { Err(EvalError::ModuloByZero) } else { Ok(Ratio::from_integer(lhs % rhs)) }
+            }
+            Self::Power { lhs, rhs } => {
+                let lhs = lhs.evaluate()?;
+                let rhs = require_integer(rhs.evaluate()?).map_err(|_| EvalError::NonIntegerExponent)?;
+                if rhs >= 0 {
+                    Ok(lhs.pow(rhs as i32))
+                }
+                else if lhs.is_zero() {
+                    Err(EvalError::DivideByZero)
+                }
+                else {
+                    Ok(lhs.pow(rhs as i32))
+                }
+            }
+            Self::Abs { base } => Ok(base.evaluate()?.abs()),
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use num_rational::Ratio;
+use std::fmt::{Debug, Display, Formatter};
+
+mod display;
+mod eval;
+mod parser;
+mod vm;
+
+pub use eval::EvalError;
+pub use parser::ParseError;
+pub use vm::{Instruction, Program};
+
+/// A solved equation: the target value paired with the expression that produces it.
+#[derive(Clone)]
+pub struct Record {
+    pub e: Expression,
+    pub n: Ratio<i64>,
+}
+
+/// An arithmetic expression tree built from digit concatenation and the basic operators.
+#[derive(Clone)]
+pub enum Expression {
+    Atomic { number: i64 },
+    Negative { base: Box<Expression> },
+    Concat { lhs: Box<Expression>, rhs: Box<Expression> },
+    Plus { lhs: Box<Expression>, rhs: Box<Expression> },
+    Minus { lhs: Box<Expression>, rhs: Box<Expression> },
+    Times { lhs: Box<Expression>, rhs: Box<Expression> },
+    Divide { lhs: Box<Expression>, rhs: Box<Expression> },
+    /// `lhs % rhs`, the remainder of `lhs ÷ rhs`.
+    Modulo { lhs: Box<Expression>, rhs: Box<Expression> },
+    /// `lhs ^ rhs`, right-associative.
+    Power { lhs: Box<Expression>, rhs: Box<Expression> },
+    /// `|base|`.
+    Abs { base: Box<Expression> },
+}
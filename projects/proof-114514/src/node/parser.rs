@@ -0,0 +1,173 @@
+use super::*;
+use std::ops::Range;
+use std::str::FromStr;
+
+/// An error produced while parsing an [`Expression`], pointing at the offending byte span.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub message: String,
+    pub span: Range<usize>,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "parse error at {}..{}: {}", self.span.start, self.span.end, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl FromStr for Expression {
+    type Err = ParseError;
+
+    /// Parse the textual form emitted by `Display for Expression` back into a tree, including the
+    /// `×`/`÷` operators this crate prints. Concatenation is only read as a run of digits: a
+    /// `Concat` whose operand prints in parentheses or bars, such as `(1+2)3`, is rejected.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser { input: s, pos: 0 };
+        let expression = parser.parse_additive()?;
+        parser.skip_ws();
+        if parser.pos != s.len() {
+            return Err(parser.error_at(parser.pos, "unexpected trailing input"));
+        }
+        Ok(expression)
+    }
+}
+
+struct Parser<'i> {
+    input: &'i str,
+    pos: usize,
+}
+
+impl<'i> Parser<'i> {
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    fn skip_ws(&mut self) {
+        while let Some(c) = self.peek() {
+            if !c.is_whitespace() {
+                break;
+            }
+            self.pos += c.len_utf8();
+        }
+    }
+
+    fn error_at(&self, at: usize, message: impl Into<String>) -> ParseError {
+        ParseError { message: message.into(), span: at..at }
+    }
+
+    /// additive := multiplicative (('+' | '-') multiplicative)*
+    fn parse_additive(&mut self) -> Result<Expression, ParseError> {
+        let mut lhs = self.parse_multiplicative()?;
+        loop {
+            self.skip_ws();
+            match self.peek() {
+                Some('+') => {
+                    self.pos += 1;
+                    let rhs = self.parse_multiplicative()?;
+                    lhs = Expression::Plus { lhs: Box::new(lhs), rhs: Box::new(rhs) };
+                }
+                Some('-') => {
+                    self.pos += 1;
+                    let rhs = self.parse_multiplicative()?;
+                    lhs = Expression::Minus { lhs: Box::new(lhs), rhs: Box::new(rhs) };
+                }
+                _ => break,
+            }
+        }
+        Ok(lhs)
+    }
+
+    /// multiplicative := unary (('×' | '÷' | '%') unary)*
+    fn parse_multiplicative(&mut self) -> Result<Expression, ParseError> {
+        let mut lhs = self.parse_unary()?;
+        loop {
+            self.skip_ws();
+            match self.peek() {
+                Some('×') => {
+                    self.pos += '×'.len_utf8();
+                    let rhs = self.parse_unary()?;
+                    lhs = Expression::Times { lhs: Box::new(lhs), rhs: Box::new(rhs) };
+                }
+                Some('÷') => {
+                    self.pos += '÷'.len_utf8();
+                    let rhs = self.parse_unary()?;
+                    lhs = Expression::Divide { lhs: Box::new(lhs), rhs: Box::new(rhs) };
+                }
+                Some('%') => {
+                    self.pos += 1;
+                    let rhs = self.parse_unary()?;
+                    lhs = Expression::Modulo { lhs: Box::new(lhs), rhs: Box::new(rhs) };
+                }
+                _ => break,
+            }
+        }
+        Ok(lhs)
+    }
+
+    /// unary := '-' unary | power
+    fn parse_unary(&mut self) -> Result<Expression, ParseError> {
+        self.skip_ws();
+        if self.peek() == Some('-') {
+            self.pos += 1;
+            let base = self.parse_unary()?;
+            return Ok(Expression::Negative { base: Box::new(base) });
+        }
+        self.parse_power()
+    }
+
+    /// power := atom ('^' unary)?, right-associative via recursing back into `parse_unary`
+    fn parse_power(&mut self) -> Result<Expression, ParseError> {
+        let base = self.parse_atom()?;
+        self.skip_ws();
+        if self.peek() == Some('^') {
+            self.pos += 1;
+            let rhs = self.parse_unary()?;
+            return Ok(Expression::Power { lhs: Box::new(base), rhs: Box::new(rhs) });
+        }
+        Ok(base)
+    }
+
+    /// atom := number | '(' additive ')' | '|' additive '|'
+    fn parse_atom(&mut self) -> Result<Expression, ParseError> {
+        self.skip_ws();
+        match self.peek() {
+            Some('(') => {
+                self.pos += 1;
+                let inner = self.parse_additive()?;
+                self.skip_ws();
+                if self.peek() != Some(')') {
+                    return Err(self.error_at(self.pos, "expected closing ')'"));
+                }
+                self.pos += 1;
+                Ok(inner)
+            }
+            Some('|') => {
+                self.pos += 1;
+                let inner = self.parse_additive()?;
+                self.skip_ws();
+                if self.peek() != Some('|') {
+                    return Err(self.error_at(self.pos, "expected closing '|'"));
+                }
+                self.pos += 1;
+                Ok(Expression::Abs { base: Box::new(inner) })
+            }
+            Some(c) if c.is_ascii_digit() => self.parse_number(),
+            Some(_) => Err(self.error_at(self.pos, "expected a number, '(' or '|'")),
+            None => Err(self.error_at(self.pos, "unexpected end of input")),
+        }
+    }
+
+    /// Digit runs are read as a single literal, so `12` parses the same way whether it was printed
+    /// from an `Atomic` or from a chain of digit `Concat`s.
+    fn parse_number(&mut self) -> Result<Expression, ParseError> {
+        let start = self.pos;
+        while matches!(self.peek(), Some(c) if c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        let digits = &self.input[start..self.pos];
+        let number: i64 = digits.parse().map_err(|_| self.error_at(start, "invalid number literal"))?;
+        Ok(Expression::Atomic { number })
+    }
+}
@@ -0,0 +1,163 @@
+use super::eval::{digit_count, require_integer};
+use super::*;
+use num_rational::Ratio;
+use num_traits::{Signed, Zero};
+
+/// A single opcode in the flattened, postfix form of an [`Expression`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Instruction {
+    Push(i64),
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Modulo,
+    Power,
+    Neg,
+    Abs,
+    Concat,
+}
+
+/// A compiled, allocation-light program equivalent to an `Expression` tree, ready to be replayed
+/// against a stack machine without recursing over boxed nodes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Program {
+    instructions: Vec<Instruction>,
+}
+
+impl Program {
+    pub fn instructions(&self) -> &[Instruction] {
+        &self.instructions
+    }
+
+    /// Run this program against a fresh operand stack and return the resulting value.
+    pub fn evaluate(&self) -> Result<Ratio<i64>, EvalError> {
+        let mut stack: Vec<Ratio<i64>> = Vec::new();
+        for instruction in &self.instructions {
+            match instruction {
+                Instruction::Push(n) => stack.push(Ratio::from_integer(*n)),
+                Instruction::Neg => {
+                    let a = stack.pop().expect("a compiled program never underflows its stack");
+                    stack.push(-a);
+                }
+                Instruction::Abs => {
+                    let a = stack.pop().expect("a compiled program never underflows its stack");
+                    stack.push(a.abs());
+                }
+                Instruction::Add => {
+                    let b = stack.pop().expect("a compiled program never underflows its stack");
+                    let a = stack.pop().expect("a compiled program never underflows its stack");
+                    stack.push(a + b);
+                }
+                Instruction::Sub => {
+                    let b = stack.pop().expect("a compiled program never underflows its stack");
+                    let a = stack.pop().expect("a compiled program never underflows its stack");
+                    stack.push(a - b);
+                }
+                Instruction::Mul => {
+                    let b = stack.pop().expect("a compiled program never underflows its stack");
+                    let a = stack.pop().expect("a compiled program never underflows its stack");
+                    stack.push(a * b);
+                }
+                Instruction::Div => {
+                    let b = stack.pop().expect("a compiled program never underflows its stack");
+                    let a = stack.pop().expect("a compiled program never underflows its stack");
+                    if b.is_zero() {
+                        return Err(EvalError::DivideByZero);
+                    }
+                    stack.push(a / b);
+                }
+                Instruction::Modulo => {
+                    let b = stack.pop().expect("a compiled program never underflows its stack");
+                    let a = stack.pop().expect("a compiled program never underflows its stack");
+                    let (a, b) = (require_integer(a)?, require_integer(b)?);
+                    if b == 0 {
+                        return Err(EvalError::ModuloByZero);
+                    }
+                    stack.push(Ratio::from_integer(a % b));
+                }
+                Instruction::Power => {
+                    let b = stack.pop().expect("a compiled program never underflows its stack");
+                    let a = stack.pop().expect("a compiled program never underflows its stack");
+                    let exponent = require_integer(b).map_err(|_| EvalError::NonIntegerExponent)?;
+                    if exponent < 0 && a.is_zero() {
+                        return Err(EvalError::DivideByZero);
+                    }
+                    stack.push(a.pow(exponent as i32));
+                }
+                Instruction::Concat => {
+                    let b = stack.pop().expect("a compiled program never underflows its stack");
+                    let a = stack.pop().expect("a compiled program never underflows its stack");
+                    let (a, b) = (require_integer(a)?, require_integer(b)?);
+                    stack.push(Ratio::from_integer(a * 10i64.pow(digit_count(b)) + b));
+                }
+            }
+        }
+        Ok(stack.pop().expect("a compiled program always leaves exactly one value on the stack"))
+    }
+}
+
+impl Expression {
+    /// Lower this tree into a flat postfix instruction stream via an iterative post-order
+    /// traversal, so large batches of candidate expressions can be evaluated without recursing
+    /// over boxed nodes.
+    pub fn compile(&self) -> Program {
+        enum Frame<'e> {
+            Visit(&'e Expression),
+            Emit(Instruction),
+        }
+
+        let mut instructions = Vec::new();
+        let mut work = vec![Frame::Visit(self)];
+        while let Some(frame) = work.pop() {
+            match frame {
+                Frame::Emit(instruction) => instructions.push(instruction),
+                Frame::Visit(Expression::Atomic { number }) => instructions.push(Instruction::Push(*number)),
+                Frame::Visit(Expression::Negative { base }) => {
+                    work.push(Frame::Emit(Instruction::Neg));
+                    work.push(Frame::Visit(base));
+                }
+                Frame::Visit(Expression::Abs { base }) => {
+                    work.push(Frame::Emit(Instruction::Abs));
+                    work.push(Frame::Visit(base));
+                }
+                Frame::Visit(Expression::Concat { lhs, rhs }) => {
+                    work.push(Frame::Emit(Instruction::Concat));
+                    work.push(Frame::Visit(rhs));
+                    work.push(Frame::Visit(lhs));
+                }
+                Frame::Visit(Expression::Plus { lhs, rhs }) => {
+                    work.push(Frame::Emit(Instruction::Add));
+                    work.push(Frame::Visit(rhs));
+                    work.push(Frame::Visit(lhs));
+                }
+                Frame::Visit(Expression::Minus { lhs, rhs }) => {
+                    work.push(Frame::Emit(Instruction::Sub));
+                    work.push(Frame::Visit(rhs));
+                    work.push(Frame::Visit(lhs));
+                }
+                Frame::Visit(Expression::Times { lhs, rhs }) => {
+                    work.push(Frame::Emit(Instruction::Mul));
+                    work.push(Frame::Visit(rhs));
+                    work.push(Frame::Visit(lhs));
+                }
+                Frame::Visit(Expression::Divide { lhs, rhs }) => {
+                    work.push(Frame::Emit(Instruction::Div));
+                    work.push(Frame::Visit(rhs));
+                    work.push(Frame::Visit(lhs));
+                }
+                Frame::Visit(Expression::Modulo { lhs, rhs }) => {
+                    work.push(Frame::Emit(Instruction::Modulo));
+                    work.push(Frame::Visit(rhs));
+                    work.push(Frame::Visit(lhs));
+                }
+                Frame::Visit(Expression::Power { lhs, rhs }) => {
+                    work.push(Frame::Emit(Instruction::Power));
+                    work.push(Frame::Visit(rhs));
+                    work.push(Frame::Visit(lhs));
+                }
+            }
+        }
+        Program { instructions }
+    }
+}